@@ -0,0 +1,493 @@
+use crate::geometry::{Circle, Vec2};
+use crate::physics::Integrator;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use std::f64::consts::TAU;
+use std::ops::Range;
+
+const DEFAULT_GRAVITY: f64 = 9.8;
+const DEFAULT_TIME_STEP: f64 = 0.1;
+
+/// How a scene should be drawn. Orthogonal to [`BallsSpec`]: the same seed
+/// positions can be rendered as visible balls or colored by their position or
+/// velocity in phase space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Draw each ball as a filled circle.
+    Balls,
+    /// Color each seed pixel by its current position, mapped through
+    /// `color_ranges`.
+    PositionPhase,
+    /// Color each seed pixel by its current velocity, mapped through
+    /// `color_ranges`.
+    VelocityPhase,
+    /// Color each seed pixel by its estimated largest Lyapunov exponent.
+    LyapunovChaos,
+}
+
+/// The two channel ranges a phase-space or chaos-map [`RenderMode`] maps its
+/// readout through before converting to a `u8` color component.
+#[derive(Debug, Clone)]
+pub struct ColorRanges {
+    pub first: Range<f64>,
+    pub second: Range<f64>,
+}
+
+/// A single ball's starting position, velocity and radius.
+#[derive(Debug, Clone)]
+pub struct BallSeed {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub radius: f64,
+}
+
+/// How a [`BallsSpec::Random`] ball's starting position is drawn.
+#[derive(Debug, Clone, Copy)]
+pub enum PositionSampling {
+    /// Uniform random position, rejection-sampled inside a disk of `radius`
+    /// centered on `center`.
+    UniformInDisk { center: Vec2, radius: f64 },
+}
+
+/// How a [`BallsSpec::Random`] ball's starting velocity is drawn.
+#[derive(Debug, Clone, Copy)]
+pub enum VelocitySampling {
+    /// Every ball starts at rest.
+    Zero,
+    /// Uniform random velocity over a disk of radius `speed`, drawn by
+    /// rejection sampling.
+    UniformInDisk { speed: f64 },
+    /// Random direction, magnitude drawn from a Gaussian with mean `speed`
+    /// and standard deviation `std_dev`.
+    Gaussian { speed: f64, std_dev: f64 },
+}
+
+/// How the balls in a scene are populated.
+#[derive(Debug, Clone)]
+pub enum BallsSpec {
+    /// A fixed, explicitly listed set of balls.
+    Explicit(Vec<BallSeed>),
+    /// One ball per pixel covering the arena's interior, all starting at
+    /// rest.
+    FillArena { ball_radius: f64 },
+    /// A Monte Carlo ensemble of `count` balls, with positions and
+    /// velocities drawn from a `StdRng` seeded with `seed` so a run can be
+    /// reproduced exactly.
+    Random {
+        count: usize,
+        ball_radius: f64,
+        seed: u64,
+        position: PositionSampling,
+        velocity: VelocitySampling,
+    },
+}
+
+impl BallsSpec {
+    pub fn seeds(&self, arena: &Circle) -> Vec<BallSeed> {
+        match self {
+            BallsSpec::Explicit(seeds) => seeds.clone(),
+            BallsSpec::FillArena { ball_radius } => {
+                let mut seeds = Vec::new();
+                for row in 0..crate::SCREEN_HEIGHT {
+                    for col in 0..crate::SCREEN_WIDTH {
+                        let position = Vec2 {
+                            x: col as f64,
+                            y: row as f64,
+                        };
+                        let distance_squared = (position.x - arena.center.x).powi(2)
+                            + (position.y - arena.center.y).powi(2);
+
+                        if distance_squared < arena.radius_squared {
+                            seeds.push(BallSeed {
+                                position,
+                                velocity: Vec2 { x: 0.0, y: 0.0 },
+                                radius: *ball_radius,
+                            });
+                        }
+                    }
+                }
+                seeds
+            }
+            BallsSpec::Random {
+                count,
+                ball_radius,
+                seed,
+                position,
+                velocity,
+            } => {
+                let mut rng = StdRng::seed_from_u64(*seed);
+                (0..*count)
+                    .map(|_| BallSeed {
+                        position: sample_position(&mut rng, *position),
+                        velocity: sample_velocity(&mut rng, *velocity),
+                        radius: *ball_radius,
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Overrides the PRNG seed of a [`BallsSpec::Random`] spec; a no-op for
+    /// every other variant. Used to apply a seed passed on the command line.
+    pub fn with_seed(mut self, new_seed: u64) -> Self {
+        if let BallsSpec::Random { seed, .. } = &mut self {
+            *seed = new_seed;
+        }
+        self
+    }
+}
+
+fn sample_position(rng: &mut StdRng, sampling: PositionSampling) -> Vec2 {
+    match sampling {
+        PositionSampling::UniformInDisk { center, radius } => loop {
+            let x = rng.gen_range(-radius..radius);
+            let y = rng.gen_range(-radius..radius);
+
+            if x * x + y * y <= radius * radius {
+                break Vec2 {
+                    x: center.x + x,
+                    y: center.y + y,
+                };
+            }
+        },
+    }
+}
+
+fn sample_velocity(rng: &mut StdRng, sampling: VelocitySampling) -> Vec2 {
+    match sampling {
+        VelocitySampling::Zero => Vec2 { x: 0.0, y: 0.0 },
+        VelocitySampling::UniformInDisk { speed } => loop {
+            let x = rng.gen_range(-speed..speed);
+            let y = rng.gen_range(-speed..speed);
+
+            if x * x + y * y <= speed * speed {
+                break Vec2 { x, y };
+            }
+        },
+        VelocitySampling::Gaussian { speed, std_dev } => {
+            let angle = rng.gen_range(0.0..TAU);
+            let magnitude = speed + std_dev * standard_normal(rng);
+            Vec2 {
+                x: magnitude * angle.cos(),
+                y: magnitude * angle.sin(),
+            }
+        }
+    }
+}
+
+/// One standard-normal sample via the Box-Muller transform.
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1 = rng.gen_range(f64::EPSILON..1.0);
+    let u2 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()
+}
+
+/// Everything a scene needs: the physical setup (gravity, time step, arena,
+/// integrator, balls, collisions) plus how to render it. Built with
+/// [`SceneBuilder`] or loaded from a RON file with [`load_from_file`].
+#[derive(Debug, Clone)]
+pub struct SceneConfig {
+    pub title: String,
+    pub gravity: f64,
+    pub time_step: f64,
+    pub arena_radius: f64,
+    pub integrator: Integrator,
+    pub collisions: bool,
+    pub balls: BallsSpec,
+    pub render_mode: RenderMode,
+    pub color_ranges: Option<ColorRanges>,
+    /// Motion-trail decay factor. `None` keeps the historical hard-clear
+    /// behavior; `Some(decay)` fades the previous frame by `decay` each
+    /// frame instead of clearing it, via [`AccumulationBuffer`].
+    ///
+    /// [`AccumulationBuffer`]: crate::render::AccumulationBuffer
+    pub trail: Option<f32>,
+}
+
+/// Fluent builder for [`SceneConfig`], so scenes declare only what's
+/// different from the defaults instead of repeating a whole event loop.
+pub struct SceneBuilder {
+    config: SceneConfig,
+}
+
+impl SceneBuilder {
+    pub fn new(title: &str) -> Self {
+        Self {
+            config: SceneConfig {
+                title: title.to_string(),
+                gravity: DEFAULT_GRAVITY,
+                time_step: DEFAULT_TIME_STEP,
+                arena_radius: crate::SCREEN_WIDTH as f64 / 2.0,
+                integrator: Integrator::SemiImplicitEuler,
+                collisions: false,
+                balls: BallsSpec::FillArena { ball_radius: 1.0 },
+                render_mode: RenderMode::Balls,
+                color_ranges: None,
+                trail: None,
+            },
+        }
+    }
+
+    pub fn with_gravity(mut self, gravity: f64) -> Self {
+        self.config.gravity = gravity;
+        self
+    }
+
+    pub fn with_time_step(mut self, time_step: f64) -> Self {
+        self.config.time_step = time_step;
+        self
+    }
+
+    pub fn with_arena(mut self, radius: f64) -> Self {
+        self.config.arena_radius = radius;
+        self
+    }
+
+    pub fn with_integrator(mut self, integrator: Integrator) -> Self {
+        self.config.integrator = integrator;
+        self
+    }
+
+    pub fn with_collisions(mut self, collisions: bool) -> Self {
+        self.config.collisions = collisions;
+        self
+    }
+
+    pub fn with_balls(mut self, balls: BallsSpec) -> Self {
+        self.config.balls = balls;
+        self
+    }
+
+    pub fn with_render_mode(mut self, render_mode: RenderMode) -> Self {
+        self.config.render_mode = render_mode;
+        self
+    }
+
+    pub fn with_color_ranges(mut self, first: Range<f64>, second: Range<f64>) -> Self {
+        self.config.color_ranges = Some(ColorRanges { first, second });
+        self
+    }
+
+    pub fn with_trail(mut self, decay: f32) -> Self {
+        self.config.trail = Some(decay);
+        self
+    }
+
+    pub fn build(self) -> SceneConfig {
+        self.config
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum IntegratorFile {
+    Euler,
+    SemiImplicitEuler,
+    VelocityVerlet,
+    Rk4,
+}
+
+impl From<IntegratorFile> for Integrator {
+    fn from(integrator: IntegratorFile) -> Self {
+        match integrator {
+            IntegratorFile::Euler => Integrator::Euler,
+            IntegratorFile::SemiImplicitEuler => Integrator::SemiImplicitEuler,
+            IntegratorFile::VelocityVerlet => Integrator::VelocityVerlet,
+            IntegratorFile::Rk4 => Integrator::Rk4,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RenderModeFile {
+    Balls,
+    PositionPhase,
+    VelocityPhase,
+    LyapunovChaos,
+}
+
+impl From<RenderModeFile> for RenderMode {
+    fn from(render_mode: RenderModeFile) -> Self {
+        match render_mode {
+            RenderModeFile::Balls => RenderMode::Balls,
+            RenderModeFile::PositionPhase => RenderMode::PositionPhase,
+            RenderModeFile::VelocityPhase => RenderMode::VelocityPhase,
+            RenderModeFile::LyapunovChaos => RenderMode::LyapunovChaos,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BallSeedFile {
+    position: (f64, f64),
+    #[serde(default)]
+    velocity: (f64, f64),
+    radius: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PositionSamplingFile {
+    UniformInDisk { center: (f64, f64), radius: f64 },
+}
+
+impl From<PositionSamplingFile> for PositionSampling {
+    fn from(sampling: PositionSamplingFile) -> Self {
+        match sampling {
+            PositionSamplingFile::UniformInDisk { center, radius } => {
+                PositionSampling::UniformInDisk {
+                    center: Vec2 {
+                        x: center.0,
+                        y: center.1,
+                    },
+                    radius,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum VelocitySamplingFile {
+    Zero,
+    UniformInDisk { speed: f64 },
+    Gaussian { speed: f64, std_dev: f64 },
+}
+
+impl From<VelocitySamplingFile> for VelocitySampling {
+    fn from(sampling: VelocitySamplingFile) -> Self {
+        match sampling {
+            VelocitySamplingFile::Zero => VelocitySampling::Zero,
+            VelocitySamplingFile::UniformInDisk { speed } => {
+                VelocitySampling::UniformInDisk { speed }
+            }
+            VelocitySamplingFile::Gaussian { speed, std_dev } => {
+                VelocitySampling::Gaussian { speed, std_dev }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BallsSpecFile {
+    Explicit(Vec<BallSeedFile>),
+    FillArena {
+        ball_radius: f64,
+    },
+    Random {
+        count: usize,
+        ball_radius: f64,
+        seed: u64,
+        position: PositionSamplingFile,
+        velocity: VelocitySamplingFile,
+    },
+}
+
+impl From<BallsSpecFile> for BallsSpec {
+    fn from(balls: BallsSpecFile) -> Self {
+        match balls {
+            BallsSpecFile::Explicit(seeds) => BallsSpec::Explicit(
+                seeds
+                    .into_iter()
+                    .map(|seed| BallSeed {
+                        position: Vec2 {
+                            x: seed.position.0,
+                            y: seed.position.1,
+                        },
+                        velocity: Vec2 {
+                            x: seed.velocity.0,
+                            y: seed.velocity.1,
+                        },
+                        radius: seed.radius,
+                    })
+                    .collect(),
+            ),
+            BallsSpecFile::FillArena { ball_radius } => BallsSpec::FillArena { ball_radius },
+            BallsSpecFile::Random {
+                count,
+                ball_radius,
+                seed,
+                position,
+                velocity,
+            } => BallsSpec::Random {
+                count,
+                ball_radius,
+                seed,
+                position: position.into(),
+                velocity: velocity.into(),
+            },
+        }
+    }
+}
+
+fn default_gravity() -> f64 {
+    DEFAULT_GRAVITY
+}
+
+fn default_time_step() -> f64 {
+    DEFAULT_TIME_STEP
+}
+
+/// On-disk form of a [`SceneConfig`], loaded via [`load_from_file`]. Field
+/// names match the `SceneConfig` fields they populate.
+#[derive(Debug, Deserialize)]
+struct SceneConfigFile {
+    title: String,
+    #[serde(default = "default_gravity")]
+    gravity: f64,
+    #[serde(default = "default_time_step")]
+    time_step: f64,
+    arena_radius: f64,
+    #[serde(default)]
+    integrator: Option<IntegratorFile>,
+    #[serde(default)]
+    collisions: bool,
+    balls: BallsSpecFile,
+    #[serde(default)]
+    render_mode: Option<RenderModeFile>,
+    #[serde(default)]
+    color_ranges: Option<((f64, f64), (f64, f64))>,
+    #[serde(default)]
+    trail: Option<f32>,
+}
+
+impl From<SceneConfigFile> for SceneConfig {
+    fn from(file: SceneConfigFile) -> Self {
+        SceneConfig {
+            title: file.title,
+            gravity: file.gravity,
+            time_step: file.time_step,
+            arena_radius: file.arena_radius,
+            integrator: file
+                .integrator
+                .map(Integrator::from)
+                .unwrap_or(Integrator::SemiImplicitEuler),
+            collisions: file.collisions,
+            balls: file.balls.into(),
+            render_mode: file
+                .render_mode
+                .map(RenderMode::from)
+                .unwrap_or(RenderMode::Balls),
+            color_ranges: file.color_ranges.map(|(first, second)| ColorRanges {
+                first: first.0..first.1,
+                second: second.0..second.1,
+            }),
+            trail: file.trail,
+        }
+    }
+}
+
+/// Loads a [`SceneConfig`] from a RON file, so new experiments can be defined
+/// without recompiling. The file is parsed as RON only: JSON syntax (structs
+/// in braces, quoted field names, `[x, y]` pairs) is not accepted.
+pub fn load_from_file(path: &str) -> SceneConfig {
+    let contents =
+        std::fs::read_to_string(path).unwrap_or_else(|err| panic!("failed to read {path}: {err}"));
+    let file: SceneConfigFile = ron::from_str(&contents)
+        .unwrap_or_else(|err| panic!("failed to parse scene config {path}: {err}"));
+    file.into()
+}
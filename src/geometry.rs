@@ -0,0 +1,36 @@
+#[derive(Debug, Clone, Copy)]
+pub struct Vec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vec2 {
+    pub fn dot_product(&self, other: &Vec2) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn reflect(&self, normal: &Vec2) -> Vec2 {
+        let dot_product = self.dot_product(normal);
+        Vec2 {
+            x: self.x - 2.0 * dot_product * normal.x,
+            y: self.y - 2.0 * dot_product * normal.y,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: f64,
+    pub radius_squared: f64,
+}
+
+impl Circle {
+    pub fn new(center: Vec2, radius: f64) -> Self {
+        Self {
+            center,
+            radius,
+            radius_squared: radius.powi(2),
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use crate::geometry::{Circle, Vec2};
+use std::collections::{HashMap, HashSet};
+
+/// Numerical scheme used to advance a ball's position and velocity each step.
+///
+/// `Euler` and `SemiImplicitEuler` both cost one acceleration evaluation but
+/// `SemiImplicitEuler` is symplectic (bounded energy error), while plain
+/// `Euler` injects energy every step. `VelocityVerlet` is also symplectic and,
+/// for the constant gravitational acceleration used throughout this crate, is
+/// exact between bounces. `Rk4` is the general-purpose choice for
+/// acceleration that depends on position or velocity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integrator {
+    Euler,
+    SemiImplicitEuler,
+    VelocityVerlet,
+    Rk4,
+}
+
+/// Advances `center`/`velocity` by `time_step` under constant `acceleration`
+/// using the given `integrator`.
+pub fn integrate(
+    center: &mut Vec2,
+    velocity: &mut Vec2,
+    acceleration: &Vec2,
+    time_step: f64,
+    integrator: Integrator,
+) {
+    match integrator {
+        Integrator::Euler => {
+            let old_velocity = *velocity;
+            center.x += old_velocity.x * time_step;
+            center.y += old_velocity.y * time_step;
+
+            velocity.x += acceleration.x * time_step;
+            velocity.y += acceleration.y * time_step;
+        }
+        Integrator::SemiImplicitEuler => {
+            velocity.x += acceleration.x * time_step;
+            velocity.y += acceleration.y * time_step;
+
+            center.x += velocity.x * time_step;
+            center.y += velocity.y * time_step;
+        }
+        Integrator::VelocityVerlet => {
+            center.x += velocity.x * time_step + 0.5 * acceleration.x * time_step.powi(2);
+            center.y += velocity.y * time_step + 0.5 * acceleration.y * time_step.powi(2);
+
+            // Acceleration is constant (gravity only) so recomputing it after the
+            // position update is a no-op, but the half-step average is kept so
+            // this reads as the general Verlet scheme.
+            let new_acceleration = *acceleration;
+            velocity.x += 0.5 * (acceleration.x + new_acceleration.x) * time_step;
+            velocity.y += 0.5 * (acceleration.y + new_acceleration.y) * time_step;
+        }
+        Integrator::Rk4 => {
+            // State derivative is (velocity, acceleration). Acceleration doesn't
+            // depend on position or velocity here, so every stage sample is the
+            // same constant, but the four-stage structure generalizes to forces
+            // that do.
+            let k1_v = *acceleration;
+            let k2_v = *acceleration;
+            let k3_v = *acceleration;
+            let k4_v = *acceleration;
+
+            let k1_x = *velocity;
+            let k2_x = Vec2 {
+                x: velocity.x + 0.5 * time_step * k1_v.x,
+                y: velocity.y + 0.5 * time_step * k1_v.y,
+            };
+            let k3_x = Vec2 {
+                x: velocity.x + 0.5 * time_step * k2_v.x,
+                y: velocity.y + 0.5 * time_step * k2_v.y,
+            };
+            let k4_x = Vec2 {
+                x: velocity.x + time_step * k3_v.x,
+                y: velocity.y + time_step * k3_v.y,
+            };
+
+            center.x += time_step / 6.0 * (k1_x.x + 2.0 * k2_x.x + 2.0 * k3_x.x + k4_x.x);
+            center.y += time_step / 6.0 * (k1_x.y + 2.0 * k2_x.y + 2.0 * k3_x.y + k4_x.y);
+
+            velocity.x += time_step / 6.0 * (k1_v.x + 2.0 * k2_v.x + 2.0 * k3_v.x + k4_v.x);
+            velocity.y += time_step / 6.0 * (k1_v.y + 2.0 * k2_v.y + 2.0 * k3_v.y + k4_v.y);
+        }
+    }
+}
+
+/// Bounces `ball` back inside `arena` if it has crossed the boundary, reflecting
+/// `velocity` off the contact normal.
+pub fn reflect_off_arena(arena: &Circle, ball: &mut Circle, velocity: &mut Vec2) {
+    let distance_squared =
+        (ball.center.x - arena.center.x).powi(2) + (ball.center.y - arena.center.y).powi(2);
+    let arena_boundary_squared = (arena.radius - ball.radius).powi(2);
+
+    if distance_squared > arena_boundary_squared {
+        let distance = distance_squared.sqrt();
+        let normal = Vec2 {
+            x: (ball.center.x - arena.center.x) / distance,
+            y: (ball.center.y - arena.center.y) / distance,
+        };
+
+        *velocity = velocity.reflect(&normal);
+
+        ball.center.x = arena.center.x + (arena.radius - ball.radius) * normal.x;
+        ball.center.y = arena.center.y + (arena.radius - ball.radius) * normal.y;
+    }
+}
+
+pub struct BallSimulation {
+    pub arena: Circle,
+    pub ball: Circle,
+    pub velocity: Vec2,
+    pub initial_position: Vec2,
+    pub integrator: Integrator,
+}
+
+impl BallSimulation {
+    pub fn new(arena: Circle, ball: Circle, velocity: Vec2, integrator: Integrator) -> Self {
+        Self {
+            arena,
+            ball,
+            velocity,
+            initial_position: ball.center,
+            integrator,
+        }
+    }
+
+    pub fn update(&mut self, acceleration: &Vec2, time_step: f64) {
+        integrate(
+            &mut self.ball.center,
+            &mut self.velocity,
+            acceleration,
+            time_step,
+            self.integrator,
+        );
+
+        reflect_off_arena(&self.arena, &mut self.ball, &mut self.velocity);
+    }
+}
+
+/// Initial separation between a reference trajectory and its shadow, in the
+/// same units as position. Small enough to stay in the linear (tangent-space)
+/// regime the Lyapunov estimate assumes.
+pub const LYAPUNOV_SEPARATION: f64 = 1e-6;
+
+/// Tracks a reference trajectory and a shadow trajectory started
+/// `LYAPUNOV_SEPARATION` away to estimate the largest Lyapunov exponent of the
+/// billiard at a single seed point, following the standard renormalization
+/// method: advance both trajectories, measure their separation in full phase
+/// space (position and velocity), accumulate `ln(d / d0)`, then rescale the
+/// shadow back to distance `d0` from the reference along the current
+/// separation direction.
+pub struct LyapunovSample {
+    pub reference: BallSimulation,
+    pub shadow: BallSimulation,
+    pub log_sum: f64,
+    pub steps: u32,
+}
+
+impl LyapunovSample {
+    pub fn new(arena: Circle, seed: Vec2, integrator: Integrator) -> Self {
+        let zero_velocity = Vec2 { x: 0.0, y: 0.0 };
+        let reference_ball = Circle::new(seed, 1.0);
+        let shadow_ball = Circle::new(
+            Vec2 {
+                x: seed.x + LYAPUNOV_SEPARATION,
+                y: seed.y,
+            },
+            1.0,
+        );
+
+        Self {
+            reference: BallSimulation::new(arena, reference_ball, zero_velocity, integrator),
+            shadow: BallSimulation::new(arena, shadow_ball, zero_velocity, integrator),
+            log_sum: 0.0,
+            steps: 0,
+        }
+    }
+
+    pub fn step(&mut self, acceleration: &Vec2, time_step: f64) {
+        self.reference.update(acceleration, time_step);
+        self.shadow.update(acceleration, time_step);
+        self.steps += 1;
+
+        let delta_position = Vec2 {
+            x: self.shadow.ball.center.x - self.reference.ball.center.x,
+            y: self.shadow.ball.center.y - self.reference.ball.center.y,
+        };
+        let delta_velocity = Vec2 {
+            x: self.shadow.velocity.x - self.reference.velocity.x,
+            y: self.shadow.velocity.y - self.reference.velocity.y,
+        };
+        let separation = (delta_position.x.powi(2)
+            + delta_position.y.powi(2)
+            + delta_velocity.x.powi(2)
+            + delta_velocity.y.powi(2))
+        .sqrt();
+
+        if separation == 0.0 {
+            // Reference and shadow landed on exactly the same state (e.g. a
+            // simultaneous wall bounce); skip this frame's renormalization
+            // rather than dividing by zero.
+            return;
+        }
+
+        self.log_sum += (separation / LYAPUNOV_SEPARATION).ln();
+
+        let rescale = LYAPUNOV_SEPARATION / separation;
+        self.shadow.ball.center.x = self.reference.ball.center.x + delta_position.x * rescale;
+        self.shadow.ball.center.y = self.reference.ball.center.y + delta_position.y * rescale;
+        self.shadow.velocity.x = self.reference.velocity.x + delta_velocity.x * rescale;
+        self.shadow.velocity.y = self.reference.velocity.y + delta_velocity.y * rescale;
+    }
+
+    /// Current estimate of the largest Lyapunov exponent, `sum(ln(d/d0)) / (steps * time_step)`.
+    pub fn lyapunov_exponent(&self, time_step: f64) -> f64 {
+        if self.steps == 0 {
+            return 0.0;
+        }
+        self.log_sum / (self.steps as f64 * time_step)
+    }
+}
+
+/// A single ball inside a [`World`]: a shape plus the velocity that drives it.
+pub struct Ball {
+    pub shape: Circle,
+    pub velocity: Vec2,
+}
+
+/// A collection of balls sharing one arena. When `collisions` is enabled the
+/// balls collide with each other, not just the wall. Broadphase pair-finding
+/// uses a uniform spatial hash so scenes with thousands of balls stay
+/// real-time instead of paying an O(n^2) cost.
+pub struct World {
+    pub arena: Circle,
+    pub balls: Vec<Ball>,
+    pub integrator: Integrator,
+    pub collisions: bool,
+}
+
+impl World {
+    pub fn new(arena: Circle, balls: Vec<Ball>, integrator: Integrator, collisions: bool) -> Self {
+        Self {
+            arena,
+            balls,
+            integrator,
+            collisions,
+        }
+    }
+
+    pub fn update(&mut self, acceleration: &Vec2, time_step: f64) {
+        for ball in self.balls.iter_mut() {
+            integrate(
+                &mut ball.shape.center,
+                &mut ball.velocity,
+                acceleration,
+                time_step,
+                self.integrator,
+            );
+
+            reflect_off_arena(&self.arena, &mut ball.shape, &mut ball.velocity);
+        }
+
+        if self.collisions {
+            self.resolve_collisions();
+        }
+    }
+
+    /// Buckets balls into grid cells sized to roughly twice the largest ball
+    /// radius, then only tests pairs that share a cell.
+    fn resolve_collisions(&mut self) {
+        let max_radius = self
+            .balls
+            .iter()
+            .map(|ball| ball.shape.radius)
+            .fold(0.0_f64, f64::max);
+        if max_radius <= 0.0 {
+            return;
+        }
+        let cell_size = max_radius * 2.0;
+
+        let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (index, ball) in self.balls.iter().enumerate() {
+            let min_x = ((ball.shape.center.x - ball.shape.radius) / cell_size).floor() as i64;
+            let min_y = ((ball.shape.center.y - ball.shape.radius) / cell_size).floor() as i64;
+            let max_x = ((ball.shape.center.x + ball.shape.radius) / cell_size).floor() as i64;
+            let max_y = ((ball.shape.center.y + ball.shape.radius) / cell_size).floor() as i64;
+
+            for cell_x in min_x..=max_x {
+                for cell_y in min_y..=max_y {
+                    grid.entry((cell_x, cell_y)).or_default().push(index);
+                }
+            }
+        }
+
+        let mut checked_pairs = HashSet::new();
+        for cell_members in grid.values() {
+            for a in 0..cell_members.len() {
+                for b in (a + 1)..cell_members.len() {
+                    let pair = (
+                        cell_members[a].min(cell_members[b]),
+                        cell_members[a].max(cell_members[b]),
+                    );
+                    if checked_pairs.insert(pair) {
+                        self.resolve_pair(pair.0, pair.1);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves an elastic, equal-mass collision between balls `i` and `j`
+    /// (`i < j`) if they currently overlap, then separates them so they no
+    /// longer do.
+    fn resolve_pair(&mut self, i: usize, j: usize) {
+        let (head, tail) = self.balls.split_at_mut(j);
+        let a = &mut head[i];
+        let b = &mut tail[0];
+
+        let delta = Vec2 {
+            x: b.shape.center.x - a.shape.center.x,
+            y: b.shape.center.y - a.shape.center.y,
+        };
+        let distance_squared = delta.x.powi(2) + delta.y.powi(2);
+        let radius_sum = a.shape.radius + b.shape.radius;
+
+        if distance_squared >= radius_sum.powi(2) || distance_squared == 0.0 {
+            return;
+        }
+
+        let distance = distance_squared.sqrt();
+        let normal = Vec2 {
+            x: delta.x / distance,
+            y: delta.y / distance,
+        };
+
+        let a_normal_speed = a.velocity.dot_product(&normal);
+        let b_normal_speed = b.velocity.dot_product(&normal);
+        let normal_speed_delta = b_normal_speed - a_normal_speed;
+
+        a.velocity.x += normal_speed_delta * normal.x;
+        a.velocity.y += normal_speed_delta * normal.y;
+        b.velocity.x -= normal_speed_delta * normal.x;
+        b.velocity.y -= normal_speed_delta * normal.y;
+
+        let push = (radius_sum - distance) / 2.0;
+        a.shape.center.x -= normal.x * push;
+        a.shape.center.y -= normal.y * push;
+        b.shape.center.x += normal.x * push;
+        b.shape.center.y += normal.y * push;
+    }
+}
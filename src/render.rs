@@ -0,0 +1,117 @@
+use crate::geometry::Circle;
+use crate::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use std::ops::Range;
+
+pub fn map_to_range(num: f64, from: &Range<f64>, to: &Range<f64>) -> f64 {
+    (num - from.start) / (from.end - from.start) * (to.end - to.start) + to.start
+}
+
+pub fn clear_frame(color: &[u8; 4], frame: &mut [u8]) {
+    for pixel in frame.chunks_exact_mut(4) {
+        pixel.copy_from_slice(color);
+    }
+}
+
+/// Destination for a single drawn pixel. Implemented by the raw `u8` RGBA
+/// frame handed to `pixels` and by [`AccumulationBuffer`], so `draw_circle`
+/// and `set_pixel` work unchanged whichever one a scene is drawing into.
+pub trait PixelSink {
+    fn set_pixel(&mut self, x: usize, y: usize, color: &[u8; 4]);
+}
+
+impl PixelSink for [u8] {
+    fn set_pixel(&mut self, x: usize, y: usize, color: &[u8; 4]) {
+        if x >= SCREEN_WIDTH || y >= SCREEN_HEIGHT {
+            return;
+        }
+
+        let index = (y * SCREEN_WIDTH + x) * 4;
+        self[index..index + 4].copy_from_slice(color);
+    }
+}
+
+pub fn draw_circle<S: PixelSink + ?Sized>(circle: &Circle, color: &[u8; 4], sink: &mut S) {
+    let row_start = (circle.center.y - circle.radius).round().max(0.0) as usize;
+    let row_end = (circle.center.y + circle.radius)
+        .ceil()
+        .min(SCREEN_HEIGHT as f64) as usize;
+    let col_start = (circle.center.x - circle.radius).floor().max(0.0) as usize;
+    let col_end = (circle.center.x + circle.radius)
+        .ceil()
+        .min(SCREEN_WIDTH as f64) as usize;
+
+    let mut pixel_count = 0;
+    for row in row_start..row_end {
+        for col in col_start..col_end {
+            let distance_squared =
+                (col as f64 - circle.center.x).powi(2) + (row as f64 - circle.center.y).powi(2);
+
+            if distance_squared < circle.radius_squared {
+                sink.set_pixel(col, row, color);
+                pixel_count += 1;
+            }
+        }
+    }
+
+    if pixel_count == 0 {
+        let x = circle.center.x.round() as i32;
+        let y = circle.center.y.round() as i32;
+
+        if x >= 0 && x < SCREEN_WIDTH as i32 && y >= 0 && y < SCREEN_HEIGHT as i32 {
+            sink.set_pixel(x as usize, y as usize, color);
+        }
+    }
+}
+
+pub fn set_pixel<S: PixelSink + ?Sized>(x: usize, y: usize, color: &[u8; 4], sink: &mut S) {
+    sink.set_pixel(x, y, color);
+}
+
+/// Backing buffer for motion-trail rendering: instead of clearing to black
+/// every frame, the previous frame is scaled down by `decay` (`0.0` hard
+/// clears, close to `1.0` leaves long trails) before the newly drawn shapes
+/// are composited on top, then [`tone_map`](AccumulationBuffer::tone_map)
+/// converts the accumulated `f32` channels down to the `u8` RGBA frame
+/// `pixels` expects.
+pub struct AccumulationBuffer {
+    decay: f32,
+    channels: Vec<f32>,
+}
+
+impl AccumulationBuffer {
+    pub fn new(decay: f32) -> Self {
+        Self {
+            decay,
+            channels: vec![0.0; SCREEN_WIDTH * SCREEN_HEIGHT * 4],
+        }
+    }
+
+    /// Scales every channel toward zero by `decay`, fading the previous
+    /// frame instead of wiping it, so newly drawn shapes leave a trail.
+    pub fn decay_frame(&mut self) {
+        for channel in self.channels.iter_mut() {
+            *channel *= self.decay;
+        }
+    }
+
+    /// Tone-maps the accumulated `f32` channels down into the `u8` RGBA
+    /// `frame` handed to `pixels`.
+    pub fn tone_map(&self, frame: &mut [u8]) {
+        for (index, channel) in self.channels.iter().enumerate() {
+            frame[index] = channel.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+impl PixelSink for AccumulationBuffer {
+    fn set_pixel(&mut self, x: usize, y: usize, color: &[u8; 4]) {
+        if x >= SCREEN_WIDTH || y >= SCREEN_HEIGHT {
+            return;
+        }
+
+        let index = (y * SCREEN_WIDTH + x) * 4;
+        for channel in 0..4 {
+            self.channels[index + channel] = color[channel] as f32;
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use crate::config::{BallsSpec, ColorRanges, RenderMode, SceneConfig};
+use crate::geometry::{Circle, Vec2};
+use crate::physics::{Ball, BallSimulation, LyapunovSample, World};
+use crate::render::{clear_frame, draw_circle, map_to_range, set_pixel, AccumulationBuffer};
+use crate::{ARENA_COLOR, BALL_COLOR, CLEAR_COLOR, PIXEL_SIZE, SCREEN_HEIGHT, SCREEN_WIDTH};
+use pixels::{Pixels, SurfaceTexture};
+use winit::{
+    dpi::LogicalSize,
+    event,
+    event::Event,
+    event_loop::EventLoop,
+    window::{Window, WindowBuilder},
+};
+
+fn initialize_scene(window_title: &str) -> (EventLoop<()>, Window, Pixels) {
+    let event_loop = EventLoop::new();
+    let window_size = LogicalSize::new(
+        (SCREEN_WIDTH * PIXEL_SIZE) as f64,
+        (SCREEN_HEIGHT * PIXEL_SIZE) as f64,
+    );
+    let window = WindowBuilder::new()
+        .with_title(window_title)
+        .with_inner_size(window_size)
+        .with_resizable(false)
+        .build(&event_loop)
+        .unwrap();
+    let pixels = Pixels::new(
+        SCREEN_WIDTH as u32,
+        SCREEN_HEIGHT as u32,
+        SurfaceTexture::new(
+            window.inner_size().width,
+            window.inner_size().height,
+            &window,
+        ),
+    )
+    .unwrap();
+
+    (event_loop, window, pixels)
+}
+
+/// Falls back to the historical magic-number ranges when a config doesn't
+/// set its own `color_ranges`.
+fn default_color_ranges(render_mode: RenderMode, arena: &Circle) -> ColorRanges {
+    match render_mode {
+        RenderMode::PositionPhase => ColorRanges {
+            first: arena.center.x - arena.radius..arena.center.x + arena.radius,
+            second: arena.center.y - arena.radius..arena.center.y + arena.radius,
+        },
+        RenderMode::VelocityPhase => {
+            let max_velocity = SCREEN_WIDTH as f64 / 10.0 * 2.5;
+            ColorRanges {
+                first: 0.0..max_velocity,
+                second: 0.0..max_velocity,
+            }
+        }
+        RenderMode::LyapunovChaos => ColorRanges {
+            first: -0.5..2.5,
+            second: 0.0..0.0,
+        },
+        RenderMode::Balls => ColorRanges {
+            first: 0.0..0.0,
+            second: 0.0..0.0,
+        },
+    }
+}
+
+/// Runs a scene described by `config`: builds the arena, balls and
+/// simulation it calls for, then drives one `winit` event loop whose
+/// rendering is picked by `config.render_mode`. Replaces the five
+/// copy-pasted per-scene event loops this crate used to have.
+pub fn run_scene(config: SceneConfig) {
+    println!("Running {}", config.title);
+    let (event_loop, window, mut pixels) = initialize_scene(&config.title);
+
+    let arena = Circle::new(
+        Vec2 {
+            x: SCREEN_WIDTH as f64 / 2.0,
+            y: SCREEN_HEIGHT as f64 / 2.0,
+        },
+        config.arena_radius,
+    );
+    let acceleration = Vec2 {
+        x: 0.0,
+        y: config.gravity,
+    };
+    let time_step = config.time_step;
+    let integrator = config.integrator;
+    let seeds = config.balls.seeds(&arena);
+
+    match config.render_mode {
+        RenderMode::Balls => {
+            let balls = seeds
+                .into_iter()
+                .map(|seed| Ball {
+                    shape: Circle::new(seed.position, seed.radius),
+                    velocity: seed.velocity,
+                })
+                .collect();
+            let mut world = World::new(arena, balls, integrator, config.collisions);
+            let mut accumulation = config.trail.map(AccumulationBuffer::new);
+
+            event_loop.run(move |event, _, control_flow| match event {
+                Event::WindowEvent {
+                    window_id: _,
+                    event: event::WindowEvent::CloseRequested,
+                } => {
+                    control_flow.set_exit();
+                }
+                Event::MainEventsCleared => {
+                    world.update(&acceleration, time_step);
+                    window.request_redraw();
+                }
+                Event::RedrawRequested(_) => {
+                    let frame = pixels.frame_mut();
+
+                    if let Some(buffer) = accumulation.as_mut() {
+                        buffer.decay_frame();
+                        draw_circle(&world.arena, &ARENA_COLOR, buffer);
+                        for ball in world.balls.iter() {
+                            draw_circle(&ball.shape, &BALL_COLOR, buffer);
+                        }
+                        buffer.tone_map(frame);
+                    } else {
+                        clear_frame(&CLEAR_COLOR, frame);
+                        draw_circle(&world.arena, &ARENA_COLOR, frame);
+                        for ball in world.balls.iter() {
+                            draw_circle(&ball.shape, &BALL_COLOR, frame);
+                        }
+                    }
+
+                    pixels.render().unwrap();
+                }
+                _ => {}
+            });
+        }
+        RenderMode::PositionPhase | RenderMode::VelocityPhase => {
+            let mut simulations: Vec<BallSimulation> = seeds
+                .into_iter()
+                .map(|seed| {
+                    BallSimulation::new(
+                        arena,
+                        Circle::new(seed.position, seed.radius),
+                        seed.velocity,
+                        integrator,
+                    )
+                })
+                .collect();
+            let color_ranges = config
+                .color_ranges
+                .unwrap_or_else(|| default_color_ranges(config.render_mode, &arena));
+            let render_mode = config.render_mode;
+            let u8_range = if render_mode == RenderMode::VelocityPhase {
+                100.0..255.0
+            } else {
+                0.0..255.0
+            };
+            let mut accumulation = config.trail.map(AccumulationBuffer::new);
+
+            event_loop.run(move |event, _, control_flow| match event {
+                Event::WindowEvent {
+                    window_id: _,
+                    event: event::WindowEvent::CloseRequested,
+                } => {
+                    control_flow.set_exit();
+                }
+                Event::MainEventsCleared => {
+                    for simulation in simulations.iter_mut() {
+                        simulation.update(&acceleration, time_step);
+                    }
+                    window.request_redraw();
+                }
+                Event::RedrawRequested(_) => {
+                    let frame = pixels.frame_mut();
+
+                    if let Some(buffer) = accumulation.as_mut() {
+                        buffer.decay_frame();
+                    } else {
+                        clear_frame(&CLEAR_COLOR, frame);
+                    }
+
+                    for simulation in simulations.iter() {
+                        let x = simulation.initial_position.x.round() as usize;
+                        let y = simulation.initial_position.y.round() as usize;
+
+                        let color = if render_mode == RenderMode::PositionPhase {
+                            let color_r = map_to_range(
+                                simulation.ball.center.x,
+                                &color_ranges.first,
+                                &u8_range,
+                            )
+                            .round() as u8;
+                            let color_g = map_to_range(
+                                simulation.ball.center.y,
+                                &color_ranges.second,
+                                &u8_range,
+                            )
+                            .round() as u8;
+                            [color_r, color_g, 0, 255]
+                        } else {
+                            let color_g =
+                                map_to_range(simulation.velocity.x, &color_ranges.first, &u8_range)
+                                    .round() as u8;
+                            let color_b = map_to_range(
+                                simulation.velocity.y,
+                                &color_ranges.second,
+                                &u8_range,
+                            )
+                            .round() as u8;
+                            [0, color_g, color_b, 255]
+                        };
+
+                        if let Some(buffer) = accumulation.as_mut() {
+                            set_pixel(x, y, &color, buffer);
+                        } else {
+                            set_pixel(x, y, &color, frame);
+                        }
+                    }
+
+                    if let Some(buffer) = accumulation.as_ref() {
+                        buffer.tone_map(frame);
+                    }
+
+                    pixels.render().unwrap();
+                }
+                _ => {}
+            });
+        }
+        RenderMode::LyapunovChaos => {
+            let mut samples: Vec<LyapunovSample> = seeds
+                .into_iter()
+                .map(|seed| LyapunovSample::new(arena, seed.position, integrator))
+                .collect();
+            let color_ranges = config
+                .color_ranges
+                .unwrap_or_else(|| default_color_ranges(config.render_mode, &arena));
+            let u8_range = 0.0..255.0;
+            let mut accumulation = config.trail.map(AccumulationBuffer::new);
+
+            event_loop.run(move |event, _, control_flow| match event {
+                Event::WindowEvent {
+                    window_id: _,
+                    event: event::WindowEvent::CloseRequested,
+                } => {
+                    control_flow.set_exit();
+                }
+                Event::MainEventsCleared => {
+                    for sample in samples.iter_mut() {
+                        sample.step(&acceleration, time_step);
+                    }
+                    window.request_redraw();
+                }
+                Event::RedrawRequested(_) => {
+                    let frame = pixels.frame_mut();
+
+                    if let Some(buffer) = accumulation.as_mut() {
+                        buffer.decay_frame();
+                    } else {
+                        clear_frame(&CLEAR_COLOR, frame);
+                    }
+
+                    for sample in samples.iter() {
+                        let x = sample.reference.initial_position.x.round() as usize;
+                        let y = sample.reference.initial_position.y.round() as usize;
+
+                        let lambda = sample
+                            .lyapunov_exponent(time_step)
+                            .clamp(color_ranges.first.start, color_ranges.first.end);
+                        let red =
+                            map_to_range(lambda, &color_ranges.first, &u8_range).round() as u8;
+                        let blue = 255 - red;
+                        let color = [red, 0, blue, 255];
+
+                        if let Some(buffer) = accumulation.as_mut() {
+                            set_pixel(x, y, &color, buffer);
+                        } else {
+                            set_pixel(x, y, &color, frame);
+                        }
+                    }
+
+                    if let Some(buffer) = accumulation.as_ref() {
+                        buffer.tone_map(frame);
+                    }
+
+                    pixels.render().unwrap();
+                }
+                _ => {}
+            });
+        }
+    }
+}
+
+/// The six built-in scenes, selectable by number on the command line. Each
+/// is now just a declarative [`SceneConfig`] instead of its own event loop.
+pub fn preset(scene_number: u32) -> SceneConfig {
+    use crate::config::{BallSeed, PositionSampling, SceneBuilder, VelocitySampling};
+    use crate::physics::Integrator;
+
+    let screen_center = Vec2 {
+        x: SCREEN_WIDTH as f64 / 2.0,
+        y: SCREEN_HEIGHT as f64 / 2.0,
+    };
+
+    match scene_number {
+        2 => SceneBuilder::new("Chaotic System With 10 Balls")
+            .with_integrator(Integrator::SemiImplicitEuler)
+            .with_collisions(true)
+            .with_trail(0.9)
+            .with_balls(BallsSpec::Random {
+                count: 10,
+                ball_radius: SCREEN_WIDTH as f64 / 100.0,
+                seed: 42,
+                position: PositionSampling::UniformInDisk {
+                    center: screen_center,
+                    radius: SCREEN_WIDTH as f64 / 100.0,
+                },
+                velocity: VelocitySampling::Gaussian {
+                    speed: 0.0,
+                    std_dev: 1.0,
+                },
+            })
+            .build(),
+        3 => SceneBuilder::new("Ball Per Pixel")
+            .with_integrator(Integrator::SemiImplicitEuler)
+            .with_collisions(true)
+            .with_balls(BallsSpec::FillArena { ball_radius: 1.0 })
+            .build(),
+        4 => SceneBuilder::new("Position Phase Space")
+            .with_integrator(Integrator::VelocityVerlet)
+            .with_balls(BallsSpec::FillArena { ball_radius: 1.0 })
+            .with_render_mode(RenderMode::PositionPhase)
+            .build(),
+        5 => SceneBuilder::new("Velocity Phase Space")
+            .with_integrator(Integrator::Rk4)
+            .with_balls(BallsSpec::FillArena { ball_radius: 1.0 })
+            .with_render_mode(RenderMode::VelocityPhase)
+            .build(),
+        6 => SceneBuilder::new("Lyapunov Exponent Chaos Map")
+            .with_integrator(Integrator::VelocityVerlet)
+            .with_balls(BallsSpec::FillArena { ball_radius: 1.0 })
+            .with_render_mode(RenderMode::LyapunovChaos)
+            .build(),
+        _ => SceneBuilder::new("Single Ball")
+            .with_integrator(Integrator::Euler)
+            .with_balls(BallsSpec::Explicit(vec![BallSeed {
+                position: screen_center,
+                velocity: Vec2 { x: 10.0, y: 0.0 },
+                radius: SCREEN_WIDTH as f64 / 100.0,
+            }]))
+            .build(),
+    }
+}